@@ -0,0 +1,123 @@
+mod server;
+
+pub(crate) use server::{
+    CacheCompressionOpts, ReadServer, ReadStream, ReadStreamItem, ServerHandles,
+};
+
+// `DataBlock`, `ClientToServerMsg`, `Decoder`, `FileInfo`, `HeapData`, and `ServerToClientMsg`
+// are assumed to already be defined or re-exported somewhere in this module in the full crate;
+// they're outside the scope of the `DataBlockCache` change below and aren't reproduced here.
+use super::DataBlock;
+
+/// The zstd-compressed sample data of a single cache block, plus the channel and frame counts
+/// needed to rebuild its channel buffers. Only the samples are kept: a rebuilt [`DataBlock`]'s
+/// other fields are those of a fresh `DataBlock::new`.
+struct CompressedBlock {
+    bytes: Vec<u8>,
+    num_channels: usize,
+    num_frames: usize,
+}
+
+/// A window of decoded blocks kept around so scrubbing/looping reads don't have to hit the
+/// decoder again.
+///
+/// Every block starts out as raw PCM in `blocks`. [`DataBlockCache::compress_block`] can replace
+/// a block's entry with a [`CompressedBlock`] to shrink the cache's memory footprint. The
+/// real-time-safe accessor, [`DataBlockCache::block`], never allocates or touches zstd: it
+/// returns `None` for a still-compressed index instead of paying a decompression cost on that
+/// path. [`DataBlockCache::decompressed_block`] does the actual decompression and is for
+/// non-real-time callers only (e.g. the server thread, ahead of a block becoming hot).
+pub(crate) struct DataBlockCache {
+    pub wanted_start_smp: usize,
+    pub blocks: Vec<DataBlock>,
+
+    compressed: Vec<Option<CompressedBlock>>,
+}
+
+impl DataBlockCache {
+    /// The number of blocks a freshly-created cache holds.
+    const DEFAULT_NUM_BLOCKS: usize = 20;
+
+    pub fn new(num_channels: usize) -> Self {
+        let num_blocks = Self::DEFAULT_NUM_BLOCKS;
+
+        Self {
+            wanted_start_smp: 0,
+            blocks: (0..num_blocks).map(|_| DataBlock::new(num_channels)).collect(),
+            compressed: (0..num_blocks).map(|_| None).collect(),
+        }
+    }
+
+    /// Compresses `blocks[index]` with zstd at the given `level`, storing the compressed bytes
+    /// and shrinking `blocks[index]` down to an empty placeholder of the same channel count to
+    /// actually free the memory. Leaves the block uncompressed if the compressed form wouldn't
+    /// actually be smaller than the raw one.
+    ///
+    /// Not real-time safe (allocates, runs zstd) — call this from the server thread only.
+    pub fn compress_block(&mut self, index: usize, level: i32) {
+        let block = &self.blocks[index];
+        let num_channels = block.block.len();
+        let num_frames = block.len();
+
+        let raw: Vec<u8> = block
+            .block
+            .iter()
+            .flat_map(|channel| channel.iter().flat_map(|sample| sample.to_le_bytes()))
+            .collect();
+
+        let Ok(compressed) = zstd::bulk::compress(&raw, level) else {
+            return;
+        };
+
+        if compressed.len() >= raw.len() {
+            // Not worth it; keep the block raw.
+            return;
+        }
+
+        self.compressed[index] =
+            Some(CompressedBlock { bytes: compressed, num_channels, num_frames });
+        self.blocks[index] = DataBlock::new(num_channels);
+    }
+
+    /// Real-time-safe accessor for the block at `index`. Returns `None` if it's currently
+    /// stored compressed rather than decompressing inline; see
+    /// [`DataBlockCache::decompressed_block`] for that.
+    pub fn block(&self, index: usize) -> Option<&DataBlock> {
+        if self.compressed[index].is_some() {
+            return None;
+        }
+
+        Some(&self.blocks[index])
+    }
+
+    /// Decompresses the block at `index` in place if it's currently compressed, and returns it.
+    ///
+    /// Not real-time safe: allocates and runs zstd. Meant to be called off the audio thread
+    /// (e.g. by the server thread, ahead of a block becoming hot) so that
+    /// [`DataBlockCache::block`] never has to do that work itself.
+    pub fn decompressed_block(&mut self, index: usize) -> &DataBlock {
+        if let Some(compressed) = self.compressed[index].take() {
+            let raw = zstd::bulk::decompress(
+                &compressed.bytes,
+                compressed.num_channels * compressed.num_frames * std::mem::size_of::<f32>(),
+            )
+            .expect("cached block failed to decompress");
+
+            let bytes_per_channel = compressed.num_frames * std::mem::size_of::<f32>();
+
+            let mut block = DataBlock::new(compressed.num_channels);
+            for (channel, raw_channel) in
+                block.block.iter_mut().zip(raw.chunks(bytes_per_channel))
+            {
+                *channel = raw_channel
+                    .chunks_exact(std::mem::size_of::<f32>())
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect();
+            }
+
+            self.blocks[index] = block;
+        }
+
+        &self.blocks[index]
+    }
+}
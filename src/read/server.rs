@@ -1,5 +1,9 @@
 use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
 
+use futures_core::Stream;
 use rtrb::{Consumer, Producer, RingBuffer};
 
 use crate::SERVER_WAIT_TIME;
@@ -8,33 +12,194 @@ use super::{
     ClientToServerMsg, DataBlock, DataBlockCache, Decoder, FileInfo, HeapData, ServerToClientMsg,
 };
 
+/// A wakeup hook shared between [`ReadServer`] and [`ReadStream`].
+///
+/// The server holds a clone and calls [`WakeSlot::wake`] right after it pushes a message into
+/// `to_client_tx`. [`ReadStream::poll_next`] registers the polling task's `Waker` here before
+/// returning `Poll::Pending`, so the executor resumes it instead of the caller having to poll
+/// in a loop.
+#[derive(Clone, Default)]
+pub(crate) struct WakeSlot(Arc<Mutex<Option<Waker>>>);
+
+impl WakeSlot {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn register(&self, waker: &Waker) {
+        *self.0.lock().unwrap() = Some(waker.clone());
+    }
+
+    fn wake(&self) {
+        if let Some(waker) = self.0.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Everything a [`ReadStream`] can yield.
+///
+/// A `Cache`/`CacheRes` round trip shares the same `to_client_tx` ring buffer as
+/// `ReadIntoBlockRes`, so a [`ReadStream`] has to surface it too rather than silently dropping
+/// it — a caller that issues cache requests while also polling the stream would otherwise lose
+/// them with no error and no way to ever observe them.
+pub enum ReadStreamItem {
+    /// The block requested by a `ReadIntoBlock` message.
+    Block(DataBlock),
+    /// The cache requested by a `Cache` message, filled in.
+    CacheRes {
+        cache_index: usize,
+        cache: DataBlockCache,
+    },
+}
+
+/// An async adapter over the ring buffer a [`ReadServer`] pushes decoded blocks into.
+///
+/// Everything about the real-time decode thread and its lock-free rtrb transport is unchanged;
+/// this only adds a notification hook so a caller can `.await` blocks via [`Stream`] instead of
+/// busy-polling `Consumer::pop` from a dedicated thread.
+pub struct ReadStream<D: Decoder + 'static> {
+    from_server_rx: Consumer<ServerToClientMsg<D>>,
+    wake_slot: WakeSlot,
+}
+
+impl<D: Decoder + 'static> ReadStream<D> {
+    /// Wraps the consumer half of a [`ReadServer`]'s `to_client_tx` ring buffer. `wake_slot`
+    /// must be the same one returned by [`ReadServer::new`] for that server.
+    pub(crate) fn new(
+        from_server_rx: Consumer<ServerToClientMsg<D>>,
+        wake_slot: WakeSlot,
+    ) -> Self {
+        Self { from_server_rx, wake_slot }
+    }
+}
+
+impl<D: Decoder + 'static> Stream for ReadStream<D> {
+    type Item = Result<ReadStreamItem, D::FatalError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match self.from_server_rx.pop() {
+                Ok(ServerToClientMsg::ReadIntoBlockRes { block, .. }) => {
+                    return Poll::Ready(Some(Ok(ReadStreamItem::Block(block))));
+                }
+                Ok(ServerToClientMsg::CacheRes { cache_index, cache }) => {
+                    return Poll::Ready(Some(Ok(ReadStreamItem::CacheRes { cache_index, cache })));
+                }
+                Ok(ServerToClientMsg::FatalError(e)) => {
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Ok(_) => {
+                    // Any other message kind isn't surfaced by this adapter. Keep draining
+                    // until a block, cache, or a fatal error turns up.
+                    continue;
+                }
+                Err(_) => {
+                    // Register before the last look at the ring buffer, so a push (or the
+                    // server shutting down) that lands after the failed `pop` above still
+                    // finds this task's waker instead of waking an empty slot.
+                    self.wake_slot.register(cx.waker());
+
+                    let abandoned = self.from_server_rx.is_abandoned();
+                    if !self.from_server_rx.is_empty() {
+                        continue;
+                    }
+                    if abandoned {
+                        return Poll::Ready(None);
+                    }
+
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+/// Opt-in zstd compression for blocks filled into a [`DataBlockCache`].
+///
+/// When set, every cache block beyond the first `hot_blocks` is run through a zstd encoder at
+/// `level` right after it is decoded, and the cache stores the compressed bytes instead of the
+/// raw PCM. This shrinks the cache's memory footprint at the cost of needing an explicit,
+/// off-audio-thread [`DataBlockCache::decompressed_block`] call before a block outside the hot
+/// window can be read; see that method's doc comment for why it isn't done implicitly on the
+/// read path.
+#[derive(Debug, Clone, Copy)]
+pub struct CacheCompressionOpts {
+    /// The zstd compression level to use.
+    pub level: i32,
+    /// The number of blocks closest to `starting_frame_in_file` to keep uncompressed, so the
+    /// most likely next reads don't pay a decompression cost.
+    pub hot_blocks: usize,
+}
+
+impl Default for CacheCompressionOpts {
+    fn default() -> Self {
+        Self { level: 3, hot_blocks: 2 }
+    }
+}
+
 pub(crate) struct ReadServer<D: Decoder + 'static> {
     to_client_tx: Producer<ServerToClientMsg<D>>,
     from_client_rx: Consumer<ClientToServerMsg>,
     close_signal_rx: Consumer<Option<HeapData>>,
 
+    file: PathBuf,
     decoder: D,
     file_info: FileInfo<D::Params>,
 
     block_pool: Vec<DataBlock>,
     cache_pool: Vec<DataBlockCache>,
 
+    cache_compression: Option<CacheCompressionOpts>,
+    /// The number of partitions to split a cache fill across, each decoded concurrently by its
+    /// own short-lived `D` instance. `1` (the default) fills the cache serially on this thread.
+    /// A cache with fewer than twice this many blocks is also filled serially.
+    cache_fill_partitions: usize,
+
+    /// Woken after every message pushed into `to_client_tx`, so a [`ReadStream`]
+    /// polling the other end can be resumed by its executor instead of busy-polling.
+    wake_slot: WakeSlot,
+
     run: bool,
 }
 
+/// Handles returned alongside the open result from [`ReadServer::new`], for the caller to drive
+/// the server's wakeup mechanisms.
+///
+/// **Contract the caller must uphold:** after pushing into `from_client_rx`'s producer or
+/// `close_signal_rx`'s producer, call `server_thread.unpark()`. Skipping this doesn't break
+/// correctness — the server still makes progress via its `park_timeout` fallback — but it does
+/// lose the whole point of this mechanism: without the `unpark()` call, the server only notices
+/// new messages once per `SERVER_WAIT_TIME`, the exact latency/CPU tradeoff `park_timeout` was
+/// introduced to eliminate.
+pub(crate) struct ServerHandles {
+    /// Must be `unpark()`-ed after every push into `from_client_rx` or `close_signal_rx`.
+    pub server_thread: std::thread::Thread,
+    /// Hand this to a [`ReadStream`] wrapping `to_client_tx`'s consumer to get async wakeups.
+    pub wake_slot: WakeSlot,
+}
+
 impl<D: Decoder + 'static> ReadServer<D> {
+    /// Spawns the server thread and blocks until the file has been opened (or failed to open).
+    ///
+    /// See [`ServerHandles`] for the wakeup contract the caller must uphold.
     pub fn new(
         file: PathBuf,
         start_frame_in_file: usize,
         to_client_tx: Producer<ServerToClientMsg<D>>,
         from_client_rx: Consumer<ClientToServerMsg>,
         close_signal_rx: Consumer<Option<HeapData>>,
-    ) -> Result<FileInfo<D::Params>, D::OpenError> {
+        cache_compression: Option<CacheCompressionOpts>,
+        cache_fill_partitions: usize,
+    ) -> (Result<FileInfo<D::Params>, D::OpenError>, ServerHandles) {
         let (mut open_tx, mut open_rx) =
             RingBuffer::<Result<FileInfo<D::Params>, D::OpenError>>::new(1).split();
 
-        std::thread::spawn(move || {
-            match D::new(file, start_frame_in_file) {
+        let wake_slot = WakeSlot::new();
+        let wake_slot_server = wake_slot.clone();
+
+        let join_handle = std::thread::spawn(move || {
+            match D::new(file.clone(), start_frame_in_file) {
                 Ok((decoder, file_info)) => {
                     // Push cannot fail because only one message is ever sent.
                     let _ = open_tx.push(Ok(file_info.clone()));
@@ -43,10 +208,14 @@ impl<D: Decoder + 'static> ReadServer<D> {
                         to_client_tx,
                         from_client_rx,
                         close_signal_rx,
+                        file,
                         decoder,
                         file_info,
                         block_pool: Vec::new(),
                         cache_pool: Vec::new(),
+                        cache_compression,
+                        cache_fill_partitions: cache_fill_partitions.max(1),
+                        wake_slot: wake_slot_server,
                         run: true,
                     });
                 }
@@ -57,9 +226,13 @@ impl<D: Decoder + 'static> ReadServer<D> {
             }
         });
 
+        // `JoinHandle::thread()` is available immediately, before the spawned closure has
+        // even started running, so this never blocks.
+        let server_thread = join_handle.thread().clone();
+
         loop {
             if let Ok(res) = open_rx.pop() {
-                return res;
+                return (res, ServerHandles { server_thread, wake_slot });
             }
 
             std::thread::sleep(SERVER_WAIT_TIME);
@@ -68,14 +241,10 @@ impl<D: Decoder + 'static> ReadServer<D> {
 
     fn run(mut self) {
         while self.run {
-            // Check for close signal.
-            if let Ok(heap_data) = self.close_signal_rx.pop() {
-                // Drop heap data here.
-                let _ = heap_data;
-                self.run = false;
-                break;
-            }
-
+            // Drain whatever is already sitting in `from_client_rx` before deciding whether to
+            // shut down. A client that enqueues a final batch of messages and then drops both
+            // producer handles in the same instant must still have those messages processed —
+            // checking abandonment first would throw them away unread.
             while let Ok(msg) = self.from_client_rx.pop() {
                 match msg {
                     ClientToServerMsg::ReadIntoBlock {
@@ -134,26 +303,17 @@ impl<D: Decoder + 'static> ReadServer<D> {
 
                         cache.wanted_start_smp = starting_frame_in_file;
 
-                        let current_frame = self.decoder.current_frame();
-
-                        // Seek to the position the client wants to cache.
-                        if let Err(e) = self.decoder.seek_to(starting_frame_in_file) {
-                            self.send_msg(ServerToClientMsg::FatalError(e));
-                            self.run = false;
-                            break;
-                        }
-
-                        // Fill the cache
-                        for block in cache.blocks.iter_mut() {
-                            if let Err(e) = self.decoder.decode_into(block) {
-                                self.send_msg(ServerToClientMsg::FatalError(e));
-                                self.run = false;
-                                break;
-                            }
-                        }
+                        // A large cache region is worth splitting across multiple short-lived
+                        // decoders; a small one isn't worth the thread overhead.
+                        let fill_result = if self.cache_fill_partitions > 1
+                            && cache.blocks.len() >= self.cache_fill_partitions * 2
+                        {
+                            self.fill_cache_parallel(&mut cache, starting_frame_in_file)
+                        } else {
+                            self.fill_cache_serial(&mut cache, starting_frame_in_file)
+                        };
 
-                        // Seek back to the previous position.
-                        if let Err(e) = self.decoder.seek_to(current_frame) {
+                        if let Err(e) = fill_result {
                             self.send_msg(ServerToClientMsg::FatalError(e));
                             self.run = false;
                             break;
@@ -168,8 +328,165 @@ impl<D: Decoder + 'static> ReadServer<D> {
                 }
             }
 
-            std::thread::sleep(SERVER_WAIT_TIME);
+            // Check for close signal.
+            if let Ok(heap_data) = self.close_signal_rx.pop() {
+                // Drop heap data here.
+                let _ = heap_data;
+                self.run = false;
+                break;
+            }
+
+            // The client was dropped without sending a close signal (e.g. it panicked).
+            // Shut down cleanly instead of spinning forever waiting for a signal that will
+            // never come.
+            if self.from_client_rx.is_abandoned() || self.close_signal_rx.is_abandoned() {
+                self.run = false;
+                break;
+            }
+
+            // Park until the client wakes us with `unpark()`, falling back to a timeout as a
+            // safety net in case a wakeup was missed.
+            std::thread::park_timeout(SERVER_WAIT_TIME);
+        }
+
+        // Wake any task parked on a `ReadStream` over `to_client_tx`'s consumer regardless of
+        // why we stopped running. `to_client_tx` is dropped first, so even exit paths that never
+        // pushed a final message (a close signal, or the client being abandoned) resume that
+        // task into a state where it sees `is_abandoned()` and resolves to `Poll::Ready(None)`
+        // instead of staying parked forever.
+        drop(self.to_client_tx);
+        self.wake_slot.wake();
+    }
+
+    /// Fills `cache` serially on this thread using the server's own long-lived decoder,
+    /// restoring its playback position afterwards.
+    fn fill_cache_serial(
+        &mut self,
+        cache: &mut DataBlockCache,
+        starting_frame_in_file: usize,
+    ) -> Result<(), D::FatalError> {
+        let current_frame = self.decoder.current_frame();
+
+        self.decoder.seek_to(starting_frame_in_file)?;
+
+        for (i, block) in cache.blocks.iter_mut().enumerate() {
+            self.decoder.decode_into(block)?;
+
+            // This index now holds fresh raw PCM; drop any stale compressed copy left over from
+            // a previous fill of a cache pulled from the pool.
+            cache.compressed[i] = None;
+
+            // Compress everything outside of the hot window. A block is left uncompressed if
+            // compressing it wouldn't actually save space.
+            if let Some(opts) = &self.cache_compression {
+                if i >= opts.hot_blocks {
+                    cache.compress_block(i, opts.level);
+                }
+            }
         }
+
+        self.decoder.seek_to(current_frame)
+    }
+
+    /// Fills `cache` by splitting its blocks into `cache_fill_partitions` contiguous partitions
+    /// and decoding each one concurrently with its own short-lived `D` instance opened on the
+    /// same file. The server's own decoder is left untouched and keeps its current position.
+    ///
+    /// `D::OpenError` and `D::FatalError` are unrelated associated types with no guaranteed
+    /// conversion between them, so an error opening a partition's decoder can't be folded into
+    /// this method's `D::FatalError` result. Every partition's decoder is therefore opened up
+    /// front, on this thread, before any work is handed off; if one of them fails to open, the
+    /// whole attempt is abandoned in favor of [`Self::fill_cache_serial`] rather than invented
+    /// error-type plumbing. Only genuine decode failures (`D::FatalError`) from that point on
+    /// are reported to the caller.
+    fn fill_cache_parallel(
+        &mut self,
+        cache: &mut DataBlockCache,
+        starting_frame_in_file: usize,
+    ) -> Result<(), D::FatalError> {
+        let partitions = self.cache_fill_partitions;
+        let blocks = std::mem::take(&mut cache.blocks);
+        let total = blocks.len();
+        let block_len = blocks.first().map(DataBlock::len).unwrap_or(0);
+
+        let base = total / partitions;
+        let rem = total % partitions;
+
+        let mut remaining = blocks.into_iter();
+        let mut partitions_blocks = Vec::with_capacity(partitions);
+        let mut frames_consumed = 0usize;
+
+        for p in 0..partitions {
+            let count = base + usize::from(p < rem);
+            if count == 0 {
+                continue;
+            }
+
+            let partition_blocks: Vec<DataBlock> = (&mut remaining).take(count).collect();
+            let partition_start_frame = starting_frame_in_file + frames_consumed * block_len;
+            frames_consumed += count;
+
+            partitions_blocks.push((partition_start_frame, partition_blocks));
+        }
+
+        // Open every partition's decoder before spawning anything. If any of them can't be
+        // opened, put the blocks back together and fall back to the serial path.
+        let mut decoders = Vec::with_capacity(partitions_blocks.len());
+        for (partition_start_frame, _) in &partitions_blocks {
+            match D::new(self.file.clone(), *partition_start_frame) {
+                Ok((decoder, _)) => decoders.push(decoder),
+                Err(_) => {
+                    cache.blocks =
+                        partitions_blocks.into_iter().flat_map(|(_, blocks)| blocks).collect();
+                    return self.fill_cache_serial(cache, starting_frame_in_file);
+                }
+            }
+        }
+
+        let handles: Vec<_> = partitions_blocks
+            .into_iter()
+            .zip(decoders)
+            .map(|((_, partition_blocks), mut decoder)| {
+                std::thread::spawn(move || -> Result<Vec<DataBlock>, D::FatalError> {
+                    let mut partition_blocks = partition_blocks;
+                    for block in partition_blocks.iter_mut() {
+                        decoder.decode_into(block)?;
+                    }
+
+                    Ok(partition_blocks)
+                })
+            })
+            .collect();
+
+        let mut filled = Vec::with_capacity(total);
+        let mut first_err = None;
+
+        for handle in handles {
+            match handle.join().expect("cache fill partition thread panicked") {
+                Ok(mut partition_blocks) => filled.append(&mut partition_blocks),
+                Err(e) => {
+                    if first_err.is_none() {
+                        first_err = Some(e);
+                    }
+                }
+            }
+        }
+
+        cache.blocks = filled;
+        // Every block above is freshly decoded raw PCM; there's nothing stale to decompress.
+        cache.compressed = (0..cache.blocks.len()).map(|_| None).collect();
+
+        if let Some(e) = first_err {
+            return Err(e);
+        }
+
+        if let Some(opts) = &self.cache_compression {
+            for i in opts.hot_blocks..cache.blocks.len() {
+                cache.compress_block(i, opts.level);
+            }
+        }
+
+        Ok(())
     }
 
     fn send_msg(&mut self, msg: ServerToClientMsg<D>) {
@@ -184,14 +501,286 @@ impl<D: Decoder + 'static> ReadServer<D> {
                 // Drop heap data here.
                 let _ = heap_data;
                 self.run = false;
-                break;
+                return;
             }
 
-            std::thread::sleep(SERVER_WAIT_TIME);
+            // The client's `Consumer` was dropped (e.g. it panicked) without ever sending a
+            // close signal. There is nobody left to drain `to_client_tx`, so waiting for it
+            // to stop being full would block forever. Shut down instead.
+            if self.to_client_tx.is_abandoned() || self.close_signal_rx.is_abandoned() {
+                self.run = false;
+                return;
+            }
+
+            std::thread::park_timeout(SERVER_WAIT_TIME);
         }
 
         // Push will never fail because we made sure a slot is available in the
         // previous step (or the server has closed).
         let _ = self.to_client_tx.push(msg);
+
+        // Resume any task polling a `ReadStream` over the other end of `to_client_tx`.
+        self.wake_slot.wake();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! `TestDecoder` is a minimal stand-in for the `Decoder` trait, whose full definition (along
+    //! with `FileInfo`'s exact fields) lives outside this file. Field values here are the best
+    //! guess consistent with how both types are used elsewhere in this module.
+
+    use std::path::PathBuf;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestError;
+
+    struct TestDecoder {
+        current_frame: usize,
+    }
+
+    impl Decoder for TestDecoder {
+        type Params = ();
+        type OpenError = TestError;
+        type FatalError = TestError;
+
+        fn new(
+            _file: PathBuf,
+            start_frame_in_file: usize,
+        ) -> Result<(Self, FileInfo<Self::Params>), Self::OpenError> {
+            Ok((
+                Self { current_frame: start_frame_in_file },
+                FileInfo { params: (), num_frames: usize::MAX, num_channels: 2, sample_rate: None },
+            ))
+        }
+
+        fn decode_into(&mut self, block: &mut DataBlock) -> Result<(), Self::FatalError> {
+            // One frame per call, stamped with the frame it was decoded at, so tests can check
+            // decode order without needing to know `DataBlock`'s real layout.
+            for channel in block.block.iter_mut() {
+                channel.clear();
+                channel.push(self.current_frame as f32);
+            }
+            self.current_frame += 1;
+            Ok(())
+        }
+
+        fn seek_to(&mut self, frame: usize) -> Result<(), Self::FatalError> {
+            self.current_frame = frame;
+            Ok(())
+        }
+
+        fn current_frame(&self) -> usize {
+            self.current_frame
+        }
+    }
+
+    /// Like [`TestDecoder`], but [`FlakyDecoder::new`] fails to open anywhere but frame 0 — for
+    /// exercising `fill_cache_parallel`'s fallback-to-serial path.
+    struct FlakyDecoder {
+        current_frame: usize,
+    }
+
+    impl Decoder for FlakyDecoder {
+        type Params = ();
+        type OpenError = TestError;
+        type FatalError = TestError;
+
+        fn new(
+            _file: PathBuf,
+            start_frame_in_file: usize,
+        ) -> Result<(Self, FileInfo<Self::Params>), Self::OpenError> {
+            if start_frame_in_file != 0 {
+                return Err(TestError);
+            }
+
+            Ok((
+                Self { current_frame: start_frame_in_file },
+                FileInfo { params: (), num_frames: usize::MAX, num_channels: 2, sample_rate: None },
+            ))
+        }
+
+        fn decode_into(&mut self, block: &mut DataBlock) -> Result<(), Self::FatalError> {
+            for channel in block.block.iter_mut() {
+                channel.clear();
+                channel.push(self.current_frame as f32);
+            }
+            self.current_frame += 1;
+            Ok(())
+        }
+
+        fn seek_to(&mut self, frame: usize) -> Result<(), Self::FatalError> {
+            self.current_frame = frame;
+            Ok(())
+        }
+
+        fn current_frame(&self) -> usize {
+            self.current_frame
+        }
+    }
+
+    /// Builds a bare `ReadServer` directly (no thread, no ring-buffer wiring to a client) so
+    /// `fill_cache_serial`/`fill_cache_parallel` can be exercised on their own.
+    fn make_server<D: Decoder + 'static>(
+        decoder: D,
+        file_info: FileInfo<D::Params>,
+        cache_fill_partitions: usize,
+    ) -> ReadServer<D> {
+        let (to_client_tx, _to_client_rx) = RingBuffer::<ServerToClientMsg<D>>::new(4).split();
+        let (_from_client_tx, from_client_rx) = RingBuffer::<ClientToServerMsg>::new(4).split();
+        let (_close_signal_tx, close_signal_rx) = RingBuffer::<Option<HeapData>>::new(1).split();
+
+        ReadServer {
+            to_client_tx,
+            from_client_rx,
+            close_signal_rx,
+            file: PathBuf::from("test.wav"),
+            decoder,
+            file_info,
+            block_pool: Vec::new(),
+            cache_pool: Vec::new(),
+            cache_compression: None,
+            cache_fill_partitions,
+            wake_slot: WakeSlot::new(),
+            run: true,
+        }
+    }
+
+    fn spawn_test_server() -> (
+        Producer<ClientToServerMsg>,
+        Producer<Option<HeapData>>,
+        Consumer<ServerToClientMsg<TestDecoder>>,
+        ServerHandles,
+    ) {
+        let (to_client_tx, to_client_rx) = RingBuffer::<ServerToClientMsg<TestDecoder>>::new(4).split();
+        let (from_client_tx, from_client_rx) = RingBuffer::<ClientToServerMsg>::new(4).split();
+        let (close_signal_tx, close_signal_rx) = RingBuffer::<Option<HeapData>>::new(1).split();
+
+        let (open_result, handles) = ReadServer::<TestDecoder>::new(
+            PathBuf::from("test.wav"),
+            0,
+            to_client_tx,
+            from_client_rx,
+            close_signal_rx,
+            None,
+            1,
+        );
+
+        assert!(open_result.is_ok(), "test decoder should never fail to open");
+
+        (from_client_tx, close_signal_tx, to_client_rx, handles)
+    }
+
+    #[test]
+    fn server_exits_when_client_is_abandoned_without_a_close_signal() {
+        let (from_client_tx, close_signal_tx, to_client_rx, handles) = spawn_test_server();
+
+        // Simulate the client panicking: both producer halves are dropped without ever pushing
+        // a close signal.
+        drop(from_client_tx);
+        drop(close_signal_tx);
+        handles.server_thread.unpark();
+
+        // The server should notice the abandonment on its own and drop `to_client_tx`, which
+        // `to_client_rx` observes as `is_abandoned()`. If the old "spin forever" bug were still
+        // here, this would never become true.
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !to_client_rx.is_abandoned() {
+            assert!(Instant::now() < deadline, "server never shut down after being abandoned");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn stream_resolves_to_none_after_a_normal_close_signal() {
+        let (_from_client_tx, mut close_signal_tx, to_client_rx, handles) = spawn_test_server();
+
+        let mut stream = ReadStream::new(to_client_rx, handles.wake_slot.clone());
+
+        let _ = close_signal_tx.push(None);
+        handles.server_thread.unpark();
+
+        let waker = Waker::noop();
+        let mut cx = Context::from_waker(waker);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            match Pin::new(&mut stream).poll_next(&mut cx) {
+                Poll::Ready(None) => break,
+                Poll::Ready(Some(_)) => continue,
+                Poll::Pending => {
+                    assert!(
+                        Instant::now() < deadline,
+                        "stream never observed the server's clean shutdown"
+                    );
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn compress_block_round_trips_sample_data() {
+        let mut cache = DataBlockCache::new(2);
+
+        // Long runs of identical samples compress well regardless of the chosen zstd level, so
+        // this doesn't depend on hitting the "didn't actually shrink" skip path.
+        let frames = 4096;
+        cache.blocks[0].block = vec![vec![0.0f32; frames], vec![0.0f32; frames]];
+        let original = cache.blocks[0].block.clone();
+
+        cache.compress_block(0, 3);
+        assert!(
+            cache.block(0).is_none(),
+            "a compressed block must not be served by the real-time accessor"
+        );
+
+        assert_eq!(cache.decompressed_block(0).block, original);
+        assert!(
+            cache.block(0).is_some(),
+            "decompressing should make the block real-time-readable again"
+        );
+    }
+
+    #[test]
+    fn fill_cache_parallel_reassembles_blocks_in_order() {
+        let (decoder, file_info) = TestDecoder::new(PathBuf::from("test.wav"), 0).unwrap();
+        let mut server = make_server(decoder, file_info, 4);
+        let mut cache = DataBlockCache::new(2);
+
+        // Materialize real per-block frame lengths (one frame each, per `TestDecoder::decode_into`)
+        // before exercising the parallel path, the same way a cache pulled from the pool already
+        // has them from a previous fill.
+        server.fill_cache_serial(&mut cache, 0).unwrap();
+
+        let starting_frame_in_file = 100;
+        server.fill_cache_parallel(&mut cache, starting_frame_in_file).unwrap();
+
+        let frames: Vec<usize> = cache.blocks.iter().map(|b| b.block[0][0] as usize).collect();
+        let expected: Vec<usize> = (0..frames.len()).map(|i| starting_frame_in_file + i).collect();
+        assert_eq!(frames, expected, "parallel fill did not reassemble blocks in order");
+    }
+
+    #[test]
+    fn fill_cache_parallel_falls_back_to_serial_when_a_partition_fails_to_open() {
+        let (decoder, file_info) = FlakyDecoder::new(PathBuf::from("test.wav"), 0).unwrap();
+        let mut server = make_server(decoder, file_info, 4);
+        let mut cache = DataBlockCache::new(2);
+
+        // The fill doesn't start at frame 0, so every partition's computed start frame is
+        // non-zero and `FlakyDecoder::new` fails to open all of them. The fill must still
+        // succeed by falling back to the serial path on the server's own (already-open, at
+        // frame 0) decoder instead of returning an error.
+        let starting_frame_in_file = 5;
+        server.fill_cache_parallel(&mut cache, starting_frame_in_file).unwrap();
+
+        let frames: Vec<usize> = cache.blocks.iter().map(|b| b.block[0][0] as usize).collect();
+        let expected: Vec<usize> = (0..frames.len()).map(|i| starting_frame_in_file + i).collect();
+        assert_eq!(frames, expected, "fallback-to-serial fill produced the wrong block contents");
     }
 }